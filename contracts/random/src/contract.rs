@@ -1,12 +1,15 @@
 use cosmwasm_std::{
-    entry_point, 
-    to_binary, 
-    Binary, 
-    Deps, 
-    DepsMut, 
-    Env, 
-    MessageInfo, 
-    Response, 
+    entry_point,
+    from_binary,
+    to_binary,
+    Addr,
+    Binary,
+    Coin,
+    Deps,
+    DepsMut,
+    Env,
+    MessageInfo,
+    Response,
     StdResult
 };
 
@@ -20,17 +23,30 @@ use secret_toolkit::permit::Permit;
 
 
 use crate::{
-    msg::{ExecuteMsg, QueryMsg, IBCLifecycleComplete, SudoMsg, InstantiateMsg, MainPageResponse}, 
-    random::{try_saving_random_number, get_saved_random_number, randomness_seed}, error::ContractError,
-    ibc::{ibc_lifecycle_complete, ibc_timeout}, 
-    state::{CellState, CELLS, Config, CONFIG, FIELD_SIZE, NETWORK_CONFIGS}, 
-    field::{valid_field_size, try_opening_cell, get_field_cells}, utils::{address_from_permit, is_powerup_list_unique, is_chain_id_list_unique}, admin::{forwards_funds, set_app_status}, powerups::{try_buying_powerups, get_user_powerups}, networks::{get_all_network_configs, get_network_config}
+    msg::{ExecuteMsg, QueryMsg, IBCLifecycleComplete, SudoMsg, InstantiateMsg, MainPageResponse, MigrateMsg, ReceiveMsg},
+    random::{try_saving_random_number, get_saved_random_number, randomness_seed, next_cell_value}, error::ContractError,
+    ibc::{ibc_lifecycle_complete, ibc_timeout},
+    merkle,
+    state::{Config, CONFIG, FIELD_SIZE, NETWORK_CONFIGS},
+    field::{valid_field_size, try_opening_cell, get_field_cells, init_field, migrate_field}, utils::{address_from_permit, is_powerup_list_unique, is_chain_id_list_unique}, admin::{forwards_funds, set_app_status}, powerups::{try_buying_powerups, get_user_powerups}, networks::{get_all_network_configs, get_network_config, network_by_token}
 };
 
 
 pub const ONE_DAY : u64 = 24 * 3600;
 
 
+/// Whether every configured `token_contract` is distinct, so
+/// [`crate::networks::network_by_token`] resolves a single network.
+fn is_token_contract_list_unique(token_contracts: &[Addr]) -> bool {
+    for (i, a) in token_contracts.iter().enumerate() {
+        if token_contracts[i + 1..].contains(a) {
+            return false;
+        }
+    }
+    true
+}
+
+
 #[entry_point]
 pub fn instantiate(
     deps: DepsMut,
@@ -50,11 +66,21 @@ pub fn instantiate(
     // win amounts
 
     let mut chain_ids: Vec<String> = Vec::with_capacity(msg.network_configs.len());
+    let mut token_contracts = Vec::new();
 
     for (denom, configs) in msg.network_configs.iter() {
 
         chain_ids.push(configs.chain_id.clone());
 
+        let mut configs = configs.clone();
+        if let Some(token_contract) = &configs.token_contract {
+            // reject a typo'd/non-canonical address up front instead of
+            // silently making this network's CW20 path unreachable
+            let token_contract = deps.api.addr_validate(token_contract.as_str())?;
+            token_contracts.push(token_contract.clone());
+            configs.token_contract = Some(token_contract);
+        }
+
         let powerup_list = configs.power_ups
                 .iter().map(|(powerup, _)| powerup.clone()).collect::<Vec<_>>();
 
@@ -73,6 +99,10 @@ pub fn instantiate(
         return Err(ContractError::DuplicateChainIds {});
     }
 
+    if !is_token_contract_list_unique(&token_contracts) {
+        return Err(ContractError::DuplicateTokenContracts {});
+    }
+
     // config
     let cell_cooldown = msg.cell_cooldown.unwrap_or(2*ONE_DAY);
     let user_cooldown = msg.user_cooldown.unwrap_or(ONE_DAY);
@@ -80,22 +110,28 @@ pub fn instantiate(
     let win_threshold = msg.win_threshold.unwrap_or(u8::MAX as u16 * 2 - 20u16);
 
 
-    CONFIG.save(deps.storage, &Config { 
-        win_threshold, 
-        cell_cooldown, 
+    CONFIG.save(deps.storage, &Config {
+        win_threshold,
+        cell_cooldown,
         user_cooldown,
+        merkle_root: [0u8; 32],
     })?;
 
     let mut ring = ChaChaRng::from_seed(
         randomness_seed(&env.block, info.sender.as_str())
     );
     let generator = ring.as_rngcore();
-    for i in 1..(field_size+1) {
-        CELLS.insert(deps.storage, &i, &CellState {
-            random: (generator.next_u32() % u8::MAX as u32) as u8,
-            open_at: env.block.time.seconds()
-        })?
+    let now = env.block.time.seconds();
+    let mut randoms = Vec::with_capacity(field_size as usize);
+    let mut open_at = Vec::with_capacity(field_size as usize);
+    for _ in 0..field_size {
+        randoms.push(next_cell_value(generator));
+        open_at.push(now);
     }
+    init_field(deps.storage, randoms, open_at)?;
+
+    // commit to the freshly generated board
+    merkle::build(deps.storage)?;
 
 
     deps.api
@@ -123,13 +159,14 @@ pub fn execute(deps: DepsMut, env: Env, info: MessageInfo, msg: ExecuteMsg) -> R
             let sender = address_from_permit(deps.as_ref(), &env, &permit)?;
                 
             try_opening_cell(
-                deps, 
-                env, 
+                deps,
+                env,
                 sender,
-                cell_id, 
-                powerups, 
+                cell_id,
+                powerups,
                 power_up_autopay,
-                info.funds
+                info.funds,
+                false,
             )
         },
 
@@ -146,6 +183,47 @@ pub fn execute(deps: DepsMut, env: Env, info: MessageInfo, msg: ExecuteMsg) -> R
             )
         },
 
+        ExecuteMsg::Receive {
+            amount,
+            msg: payload,
+            ..
+        } => {
+            // the caller is the token contract; it must be whitelisted and its
+            // amount/denom stands in for native info.funds
+            let (denom, _) = network_by_token(deps.storage, &info.sender)?;
+            let coins = vec![Coin { denom, amount }];
+
+            match from_binary(&payload)? {
+                ReceiveMsg::OpenCell {
+                    permit,
+                    cell_id,
+                    powerups,
+                    power_up_autopay,
+                } => {
+                    // a single CW20 transfer carries exactly one amount, so
+                    // there is no second coin to fund an autopay top-up with
+                    if power_up_autopay {
+                        return Err(ContractError::AutopayUnsupportedForToken {});
+                    }
+                    let sender = address_from_permit(deps.as_ref(), &env, &permit)?;
+                    try_opening_cell(
+                        deps,
+                        env,
+                        sender,
+                        cell_id,
+                        powerups,
+                        power_up_autopay,
+                        coins,
+                        true,
+                    )
+                }
+                ReceiveMsg::BuyPowerups { permit, powerups } => {
+                    let sender = address_from_permit(deps.as_ref(), &env, &permit)?;
+                    try_buying_powerups(deps, sender, powerups, coins)
+                }
+            }
+        }
+
         ExecuteMsg::SetAppStatus { status } => set_app_status(deps, info.sender, status),
 
         
@@ -171,6 +249,14 @@ pub fn execute(deps: DepsMut, env: Env, info: MessageInfo, msg: ExecuteMsg) -> R
 
 
 
+#[entry_point]
+pub fn migrate(deps: DepsMut, _env: Env, _msg: MigrateMsg) -> Result<Response, ContractError> {
+    // move any pre-existing board from the per-cell map into the packed blobs
+    migrate_field(deps.storage)?;
+    Ok(Response::default())
+}
+
+
 #[entry_point]
 pub fn query(deps: Deps, env: Env, msg: QueryMsg) -> StdResult<Binary> {
     match msg {
@@ -179,6 +265,7 @@ pub fn query(deps: Deps, env: Env, msg: QueryMsg) -> StdResult<Binary> {
         QueryMsg::GetMyPowerups { permit } => to_binary(&get_user_powerups(deps, env, permit)?),
         QueryMsg::NetworkConfig { denom } => to_binary(&get_network_config(deps, denom)),
         QueryMsg::AllNetworkConfigs {} => to_binary(&get_all_network_configs(deps)?),
+        QueryMsg::CellProof { cell_id } => to_binary(&merkle::proof(deps.storage, cell_id)?),
 
         QueryMsg::Main { permit } => to_binary(&get_main(deps, env, permit)?),
     }
@@ -217,9 +304,11 @@ pub fn get_main(
     };
     
     let network_configs = get_all_network_configs(deps)?;
+    let root = CONFIG.load(deps.storage)?.merkle_root;
     Ok(MainPageResponse {
         cells: field_res.cells,
         powerups,
-        network_configs
+        network_configs,
+        root,
     })
 }
\ No newline at end of file
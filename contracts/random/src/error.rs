@@ -0,0 +1,71 @@
+use std::fmt;
+
+use cosmwasm_std::StdError;
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
+use thiserror::Error;
+
+use crate::state::Powerup;
+
+#[derive(Error, Debug, PartialEq)]
+pub enum ContractError {
+    #[error("{0}")]
+    Std(#[from] StdError),
+
+    #[error("invalid field size")]
+    InvalidFieldSize {},
+
+    #[error("each network must define exactly three unique powerups")]
+    InvalidPowerupAmounts {},
+
+    #[error("ibc denoms require a channel id")]
+    MissingChannelId {},
+
+    #[error("duplicate chain ids across network configs")]
+    DuplicateChainIds {},
+
+    #[error("duplicate token contract across network configs")]
+    DuplicateTokenContracts {},
+
+    #[error("cell id is out of range")]
+    InvalidCell {},
+
+    #[error("denom is not accepted on this network")]
+    UnknownDenom {},
+
+    #[error("sender is not an allowed token contract")]
+    UnauthorizedToken {},
+
+    #[error("powerup autopay is not supported when paying through a token contract")]
+    AutopayUnsupportedForToken {},
+
+    #[error("no balance for powerup {kind:?}")]
+    MissingPowerup { kind: Powerup },
+
+    #[error("cell cannot be opened: {reason}")]
+    OpenRejected { reason: FailureReason },
+
+    #[error("powerups cannot be bought: {reason}")]
+    BuyRejected { reason: FailureReason },
+}
+
+/// Enumerable, machine-readable cause surfaced on lifecycle rejections so a
+/// client does not have to pattern-match on a free-form error string.
+#[derive(Serialize, Deserialize, Clone, Copy, Debug, PartialEq, Eq, JsonSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum FailureReason {
+    CooldownNotElapsed,
+    InsufficientFunds,
+    FieldClosed,
+}
+
+impl fmt::Display for FailureReason {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let token = match self {
+            FailureReason::CooldownNotElapsed => "cooldown_not_elapsed",
+            FailureReason::InsufficientFunds => "insufficient_funds",
+            FailureReason::FieldClosed => "field_closed",
+        };
+        f.write_str(token)
+    }
+}
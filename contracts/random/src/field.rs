@@ -0,0 +1,233 @@
+use cosmwasm_std::{
+    to_binary, Addr, BankMsg, Coin, CosmosMsg, Deps, DepsMut, Env, Response, StdResult, Storage,
+    Uint128, WasmMsg,
+};
+
+use crate::{
+    error::{ContractError, FailureReason},
+    merkle,
+    msg::{CellResponse, Cw20ExecuteMsg, FieldResponse},
+    powerups,
+    random::{next_cell_value, randomness_seed},
+    state::{
+        CellState, Powerup, APP_STATUS, CELLS, CELL_OPEN_AT, CELL_RANDOM, CONFIG, FIELD_SIZE,
+        LAST_OPEN, NETWORK_CONFIGS,
+    },
+};
+
+/// Boards are square and bounded so the packed blobs stay small.
+pub fn valid_field_size(size: u32) -> bool {
+    if size == 0 || size > 1024 {
+        return false;
+    }
+    let root = (size as f64).sqrt() as u32;
+    root * root == size
+}
+
+/// Allocate the packed blobs for a freshly generated board.
+pub fn init_field(storage: &mut dyn Storage, randoms: Vec<u8>, open_at: Vec<u64>) -> StdResult<()> {
+    CELL_RANDOM.save(storage, &randoms)?;
+    CELL_OPEN_AT.save(storage, &open_at)?;
+    Ok(())
+}
+
+/// Read a single cell out of the two packed blobs.
+pub fn get_cell(storage: &dyn Storage, cell_id: u32) -> StdResult<CellState> {
+    let index = (cell_id - 1) as usize;
+    Ok(CellState {
+        random: CELL_RANDOM.load(storage)?[index],
+        open_at: CELL_OPEN_AT.load(storage)?[index],
+    })
+}
+
+/// Splice a single cell back into the packed blobs, touching one byte and one
+/// timestamp each.
+pub fn set_cell(storage: &mut dyn Storage, cell_id: u32, cell: &CellState) -> StdResult<()> {
+    let index = (cell_id - 1) as usize;
+
+    let mut randoms = CELL_RANDOM.load(storage)?;
+    randoms[index] = cell.random;
+    CELL_RANDOM.save(storage, &randoms)?;
+
+    let mut open_at = CELL_OPEN_AT.load(storage)?;
+    open_at[index] = cell.open_at;
+    CELL_OPEN_AT.save(storage, &open_at)?;
+
+    Ok(())
+}
+
+/// Rebuild the response from the two packed vectors with a single read each.
+pub fn get_field_cells(deps: Deps) -> StdResult<FieldResponse> {
+    let randoms = CELL_RANDOM.load(deps.storage)?;
+    let open_at = CELL_OPEN_AT.load(deps.storage)?;
+
+    let cells = randoms
+        .into_iter()
+        .zip(open_at)
+        .enumerate()
+        .map(|(i, (random, open_at))| CellResponse {
+            cell_id: i as u32 + 1,
+            random,
+            open_at,
+        })
+        .collect();
+
+    Ok(FieldResponse { cells })
+}
+
+/// Rebuild the packed blobs from the legacy per-cell [`CELLS`] map.
+///
+/// Idempotent: a second invocation (an operator re-running the migration, or
+/// a later upgrade's `migrate` also calling this) would otherwise find
+/// `CELLS` already drained and zero out every cell, so it is a no-op once the
+/// packed blobs exist.
+pub fn migrate_field(storage: &mut dyn Storage) -> StdResult<()> {
+    if CELL_RANDOM.may_load(storage)?.is_some() {
+        return Ok(());
+    }
+
+    let field_size = FIELD_SIZE.load(storage)?;
+
+    let mut randoms = Vec::with_capacity(field_size as usize);
+    let mut open_at = Vec::with_capacity(field_size as usize);
+    for cell_id in 1..=field_size {
+        let cell = CELLS.get(storage, &cell_id).unwrap_or(CellState {
+            random: 0,
+            open_at: 0,
+        });
+        randoms.push(cell.random);
+        open_at.push(cell.open_at);
+        CELLS.remove(storage, &cell_id)?;
+    }
+
+    init_field(storage, randoms, open_at)?;
+    merkle::build(storage)?;
+    Ok(())
+}
+
+pub fn try_opening_cell(
+    deps: DepsMut,
+    env: Env,
+    sender: Addr,
+    cell_id: u32,
+    powerups_used: Vec<Powerup>,
+    power_up_autopay: bool,
+    funds: Vec<Coin>,
+    paid_with_token: bool,
+) -> Result<Response, ContractError> {
+    if !APP_STATUS.load(deps.storage).unwrap_or(true) {
+        return Err(ContractError::OpenRejected {
+            reason: FailureReason::FieldClosed,
+        });
+    }
+
+    let field_size = FIELD_SIZE.load(deps.storage)?;
+    if cell_id == 0 || cell_id > field_size {
+        return Err(ContractError::InvalidCell {});
+    }
+
+    let config = CONFIG.load(deps.storage)?;
+    let now = env.block.time.seconds();
+
+    // per-user cooldown
+    let last_open = LAST_OPEN.get(deps.storage, &sender.to_string()).unwrap_or(0);
+    if now < last_open + config.user_cooldown {
+        return Err(ContractError::OpenRejected {
+            reason: FailureReason::CooldownNotElapsed,
+        });
+    }
+
+    // per-cell cooldown
+    let mut cell = get_cell(deps.storage, cell_id)?;
+    if now < cell.open_at {
+        return Err(ContractError::OpenRejected {
+            reason: FailureReason::CooldownNotElapsed,
+        });
+    }
+
+    // pricing: the denom sent decides which network config applies
+    let coin = funds.first().ok_or(ContractError::OpenRejected {
+        reason: FailureReason::InsufficientFunds,
+    })?;
+    let network = NETWORK_CONFIGS
+        .get(deps.storage, &coin.denom)
+        .ok_or(ContractError::UnknownDenom {})?;
+    if coin.amount < network.open_price {
+        return Err(ContractError::OpenRejected {
+            reason: FailureReason::InsufficientFunds,
+        });
+    }
+
+    // spend any powerups the player chose to use, buying them first on autopay
+    powerups::spend_powerups(
+        deps.storage,
+        &sender,
+        &powerups_used,
+        power_up_autopay,
+        &network,
+        &coin.denom,
+        funds.get(1),
+    )?;
+
+    let revealed = cell.random;
+    let win = (revealed as u16) * 2 > config.win_threshold;
+
+    let mut response = Response::default();
+    let payout = if win {
+        let payout = network.open_price * Uint128::from(2u128);
+        let payout_msg: CosmosMsg = if paid_with_token {
+            // the contract's balance for this network is CW20 tokens held by
+            // the token contract, not native coins, so the win is paid out
+            // as a Transfer instead of a BankMsg::Send
+            let token_contract = network
+                .token_contract
+                .clone()
+                .ok_or(ContractError::UnknownDenom {})?;
+            WasmMsg::Execute {
+                contract_addr: token_contract.to_string(),
+                msg: to_binary(&Cw20ExecuteMsg::Transfer {
+                    recipient: sender.to_string(),
+                    amount: payout,
+                })?,
+                funds: vec![],
+            }
+            .into()
+        } else {
+            BankMsg::Send {
+                to_address: sender.to_string(),
+                amount: vec![Coin {
+                    denom: coin.denom.clone(),
+                    amount: payout,
+                }],
+            }
+            .into()
+        };
+        response = response.add_message(payout_msg);
+        payout
+    } else {
+        Uint128::zero()
+    };
+
+    // re-roll the cell and lock it behind the cooldown, then recommit the leaf
+    let mut ring =
+        rand_chacha::ChaChaRng::from_seed(randomness_seed(&env.block, sender.as_str()));
+    cell.random = next_cell_value(ring.as_rngcore());
+    cell.open_at = now + config.cell_cooldown;
+    set_cell(deps.storage, cell_id, &cell)?;
+    merkle::update_leaf(deps.storage, cell_id, &cell)?;
+
+    LAST_OPEN.insert(deps.storage, &sender.to_string(), &now)?;
+
+    let powerups_used: Vec<String> = powerups_used
+        .iter()
+        .map(|p| format!("{p:?}").to_lowercase())
+        .collect();
+
+    Ok(response
+        .add_attribute("action", "open_cell")
+        .add_attribute("cell_id", cell_id.to_string())
+        .add_attribute("revealed_value", revealed.to_string())
+        .add_attribute("outcome", if win { "win" } else { "loss" })
+        .add_attribute("powerups_used", powerups_used.join(","))
+        .add_attribute("payout", payout.to_string()))
+}
@@ -0,0 +1,223 @@
+use cosmwasm_std::{sha2::{Digest, Sha256}, StdError, StdResult, Storage};
+
+use secret_toolkit::storage::Keymap;
+
+use crate::{
+    field::get_cell,
+    msg::CellProofResponse,
+    state::{CONFIG, FIELD_SIZE},
+};
+
+/// All tree nodes keyed by `(level, index)`, level `0` being the leaves.
+/// Keeping the internal nodes lets a single cell write touch only the
+/// O(log n) path up to the root instead of rebuilding the whole tree.
+static NODES: Keymap<(u32, u32), [u8; 32]> = Keymap::new(b"merkle_nodes");
+
+fn hash(bytes: &[u8]) -> [u8; 32] {
+    Sha256::digest(bytes).into()
+}
+
+/// Leaf commitment for a single cell: `hash(cell_id || random || open_at)`.
+pub fn leaf_hash(cell_id: u32, cell: &CellState) -> [u8; 32] {
+    let mut preimage = Vec::with_capacity(4 + 1 + 8);
+    preimage.extend_from_slice(&cell_id.to_be_bytes());
+    preimage.push(cell.random);
+    preimage.extend_from_slice(&cell.open_at.to_be_bytes());
+    hash(&preimage)
+}
+
+/// Number of nodes on a given `level` for a tree with `leaves` leaves, where
+/// an odd level duplicates its last node to pair up.
+fn level_len(leaves: u32, level: u32) -> u32 {
+    let mut len = leaves.max(1);
+    for _ in 0..level {
+        len = len.div_ceil(2);
+    }
+    len
+}
+
+fn node(storage: &dyn Storage, level: u32, index: u32) -> StdResult<[u8; 32]> {
+    Ok(NODES.get(storage, &(level, index)).unwrap_or_default())
+}
+
+/// Build the whole tree from scratch, hashing every leaf once. Used on
+/// instantiation and whenever the board is rebuilt wholesale.
+pub fn build(storage: &mut dyn Storage) -> StdResult<[u8; 32]> {
+    let field_size = FIELD_SIZE.load(storage)?;
+
+    for cell_id in 1..=field_size {
+        let cell = get_cell(storage, cell_id)?;
+        NODES.insert(storage, &(0u32, cell_id - 1), &leaf_hash(cell_id, &cell))?;
+    }
+
+    let mut level = 0u32;
+    while level_len(field_size, level) > 1 {
+        let parents = level_len(field_size, level + 1);
+        for i in 0..parents {
+            let left = node(storage, level, 2 * i)?;
+            let right = node(storage, level, (2 * i + 1).min(level_len(field_size, level) - 1))?;
+            NODES.insert(storage, &(level + 1, i), &hash_pair(&left, &right))?;
+        }
+        level += 1;
+    }
+
+    let root = node(storage, level, 0)?;
+    CONFIG.update(storage, |mut c| -> StdResult<_> {
+        c.merkle_root = root;
+        Ok(c)
+    })?;
+    Ok(root)
+}
+
+fn hash_pair(left: &[u8; 32], right: &[u8; 32]) -> [u8; 32] {
+    let mut preimage = Vec::with_capacity(64);
+    preimage.extend_from_slice(left);
+    preimage.extend_from_slice(right);
+    hash(&preimage)
+}
+
+/// Recompute only the sibling path from a changed leaf up to the root.
+pub fn update_leaf(storage: &mut dyn Storage, cell_id: u32, cell: &CellState) -> StdResult<[u8; 32]> {
+    let field_size = FIELD_SIZE.load(storage)?;
+
+    let mut index = cell_id - 1;
+    NODES.insert(storage, &(0u32, index), &leaf_hash(cell_id, cell))?;
+
+    let mut level = 0u32;
+    while level_len(field_size, level) > 1 {
+        let parent = index / 2;
+        let left = node(storage, level, 2 * parent)?;
+        // Duplicate the last node when this level has an odd count.
+        let right_index = (2 * parent + 1).min(level_len(field_size, level) - 1);
+        let right = node(storage, level, right_index)?;
+        NODES.insert(storage, &(level + 1, parent), &hash_pair(&left, &right))?;
+        index = parent;
+        level += 1;
+    }
+
+    let root = node(storage, level, 0)?;
+    CONFIG.update(storage, |mut c| -> StdResult<_> {
+        c.merkle_root = root;
+        Ok(c)
+    })?;
+    Ok(root)
+}
+
+/// Ordered sibling hashes that let a client recompute the root from a leaf.
+pub fn proof(storage: &dyn Storage, cell_id: u32) -> StdResult<CellProofResponse> {
+    let field_size = FIELD_SIZE.load(storage)?;
+
+    if cell_id == 0 || cell_id > field_size {
+        return Err(StdError::generic_err("cell id is out of range"));
+    }
+
+    let leaf = node(storage, 0, cell_id - 1)?;
+    let mut siblings = Vec::new();
+
+    let mut index = cell_id - 1;
+    let mut level = 0u32;
+    while level_len(field_size, level) > 1 {
+        let sibling_index = if index % 2 == 0 { index + 1 } else { index - 1 };
+        let sibling_index = sibling_index.min(level_len(field_size, level) - 1);
+        siblings.push(node(storage, level, sibling_index)?);
+        index /= 2;
+        level += 1;
+    }
+
+    Ok(CellProofResponse {
+        cell_id,
+        leaf,
+        siblings,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use cosmwasm_std::testing::MockStorage;
+
+    use crate::{
+        field::init_field,
+        state::{CellState, Config},
+    };
+
+    fn seeded_storage(field_size: u32) -> MockStorage {
+        let mut storage = MockStorage::new();
+        FIELD_SIZE.save(&mut storage, &field_size).unwrap();
+        CONFIG
+            .save(
+                &mut storage,
+                &Config {
+                    win_threshold: 0,
+                    cell_cooldown: 0,
+                    user_cooldown: 0,
+                    merkle_root: [0u8; 32],
+                },
+            )
+            .unwrap();
+
+        let randoms = (0..field_size).map(|i| i as u8).collect();
+        let open_at = (0..field_size).map(|i| i as u64).collect();
+        init_field(&mut storage, randoms, open_at).unwrap();
+        storage
+    }
+
+    fn recompute_root(leaf: [u8; 32], siblings: &[[u8; 32]], mut index: u32) -> [u8; 32] {
+        let mut current = leaf;
+        for sibling in siblings {
+            current = if index % 2 == 0 {
+                hash_pair(&current, sibling)
+            } else {
+                hash_pair(sibling, &current)
+            };
+            index /= 2;
+        }
+        current
+    }
+
+    #[test]
+    fn proof_siblings_recompute_the_built_root() {
+        for field_size in [1u32, 2, 3, 5, 7, 8, 13] {
+            let mut storage = seeded_storage(field_size);
+            let root = build(&mut storage).unwrap();
+
+            for cell_id in 1..=field_size {
+                let cell = get_cell(&storage, cell_id).unwrap();
+                let proof = proof(&storage, cell_id).unwrap();
+                assert_eq!(proof.leaf, leaf_hash(cell_id, &cell));
+
+                let recomputed = recompute_root(proof.leaf, &proof.siblings, cell_id - 1);
+                assert_eq!(
+                    recomputed, root,
+                    "cell {cell_id} failed to recompute the root for field_size {field_size}"
+                );
+            }
+        }
+    }
+
+    #[test]
+    fn update_leaf_keeps_the_tree_consistent_with_a_full_rebuild() {
+        let field_size = 5u32;
+        let mut storage = seeded_storage(field_size);
+        build(&mut storage).unwrap();
+
+        let changed = CellState {
+            random: 200,
+            open_at: 999,
+        };
+        let incremental_root = update_leaf(&mut storage, 3, &changed).unwrap();
+
+        let mut rebuilt = seeded_storage(field_size);
+        crate::field::set_cell(&mut rebuilt, 3, &changed).unwrap();
+        let rebuilt_root = build(&mut rebuilt).unwrap();
+
+        assert_eq!(incremental_root, rebuilt_root);
+    }
+
+    #[test]
+    fn proof_rejects_out_of_range_cell_id() {
+        let storage = seeded_storage(4);
+        assert!(proof(&storage, 0).is_err());
+        assert!(proof(&storage, 5).is_err());
+    }
+}
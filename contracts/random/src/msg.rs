@@ -0,0 +1,145 @@
+use cosmwasm_std::{Binary, Uint128};
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
+
+use secret_toolkit::permit::Permit;
+
+use crate::state::{NetworkConfig, Powerup};
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct InstantiateMsg {
+    /// Number of cells on the board, defaults to 64.
+    pub field_size: Option<u32>,
+    /// Winning threshold over the `random` value, defaults to ~4%.
+    pub win_threshold: Option<u16>,
+    pub cell_cooldown: Option<u64>,
+    pub user_cooldown: Option<u64>,
+    /// Per-denom configuration keyed by the accepted denom.
+    pub network_configs: Vec<(String, NetworkConfig)>,
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum ExecuteMsg {
+    UpdateMyRandomNumber {
+        permit: Permit,
+    },
+    OpenCell {
+        permit: Permit,
+        cell_id: u32,
+        powerups: Vec<Powerup>,
+        power_up_autopay: bool,
+    },
+    BuyPowerups {
+        permit: Permit,
+        powerups: Vec<Powerup>,
+    },
+    /// CW20-style hook: a token contract forwards a transfer whose `msg`
+    /// base64-decodes to a [`ReceiveMsg`] that is then run as if the tokens
+    /// were native `info.funds`.
+    Receive {
+        sender: String,
+        amount: Uint128,
+        msg: Binary,
+    },
+    SetAppStatus {
+        status: bool,
+    },
+    ForwardsFunds {
+        to_address: String,
+        amount: Uint128,
+    },
+    IBCLifecycleComplete(IBCLifecycleComplete),
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum QueryMsg {
+    GetField {},
+    GetMyRandomNumber { permit: Permit },
+    GetMyPowerups { permit: Permit },
+    NetworkConfig { denom: String },
+    AllNetworkConfigs {},
+    /// Sibling path proving a single cell against the committed Merkle root.
+    CellProof { cell_id: u32 },
+    Main { permit: Option<Permit> },
+}
+
+/// Payload carried inside a CW20 [`ExecuteMsg::Receive`] hook.
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum ReceiveMsg {
+    OpenCell {
+        permit: Permit,
+        cell_id: u32,
+        powerups: Vec<Powerup>,
+        power_up_autopay: bool,
+    },
+    BuyPowerups {
+        permit: Permit,
+        powerups: Vec<Powerup>,
+    },
+}
+
+/// The one CW20 `ExecuteMsg` variant this contract ever sends, to pay out a
+/// token-funded win back to the token contract `info.sender` arrived through.
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum Cw20ExecuteMsg {
+    Transfer { recipient: String, amount: Uint128 },
+}
+
+/// Rebuilds the packed field blobs from the legacy per-cell map.
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct MigrateMsg {}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum SudoMsg {
+    IBCLifecycleComplete(IBCLifecycleComplete),
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum IBCLifecycleComplete {
+    IBCAck {
+        channel: String,
+        sequence: u64,
+        ack: String,
+        success: bool,
+    },
+    IBCTimeout {
+        channel: String,
+        sequence: u64,
+    },
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct CellResponse {
+    pub cell_id: u32,
+    pub random: u8,
+    pub open_at: u64,
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct FieldResponse {
+    pub cells: Vec<CellResponse>,
+}
+
+/// Inclusion proof for a single cell: the leaf hash plus the ordered sibling
+/// hashes from the leaf up to (but excluding) the root.
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct CellProofResponse {
+    pub cell_id: u32,
+    pub leaf: [u8; 32],
+    pub siblings: Vec<[u8; 32]>,
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct MainPageResponse {
+    pub cells: Vec<CellResponse>,
+    pub powerups: Option<Vec<(Powerup, u32)>>,
+    pub network_configs: Vec<(String, NetworkConfig)>,
+    /// Current Merkle root so a front-end can verify [`CellProofResponse`]s.
+    pub root: [u8; 32],
+}
@@ -0,0 +1,37 @@
+use cosmwasm_std::{Addr, Deps, StdResult, Storage};
+
+use crate::{
+    error::ContractError,
+    state::{NetworkConfig, NETWORK_CONFIGS},
+};
+
+pub fn get_network_config(deps: Deps, denom: String) -> Option<NetworkConfig> {
+    NETWORK_CONFIGS.get(deps.storage, &denom)
+}
+
+pub fn get_all_network_configs(deps: Deps) -> StdResult<Vec<(String, NetworkConfig)>> {
+    NETWORK_CONFIGS
+        .iter(deps.storage)?
+        .collect::<StdResult<Vec<_>>>()
+}
+
+/// Fetch a config by denom or fail with an enumerable error.
+pub fn network_config(storage: &dyn Storage, denom: &str) -> Result<NetworkConfig, ContractError> {
+    NETWORK_CONFIGS
+        .get(storage, &denom.to_string())
+        .ok_or(ContractError::UnknownDenom {})
+}
+
+/// Resolve the network a CW20 transfer is paying for by matching the calling
+/// token contract against the configured `token_contract`, returning the denom
+/// key alongside the config so the shared pricing applies.
+pub fn network_by_token(
+    storage: &dyn Storage,
+    token: &Addr,
+) -> Result<(String, NetworkConfig), ContractError> {
+    NETWORK_CONFIGS
+        .iter(storage)?
+        .filter_map(Result::ok)
+        .find(|(_, config)| config.token_contract.as_ref() == Some(token))
+        .ok_or(ContractError::UnauthorizedToken {})
+}
@@ -0,0 +1,124 @@
+use cosmwasm_std::{Addr, Coin, Deps, DepsMut, Env, Response, StdResult, Storage, Uint128};
+
+use secret_toolkit::{permit::Permit, storage::Keymap};
+
+use crate::{
+    error::{ContractError, FailureReason},
+    state::{NetworkConfig, Powerup},
+    utils::address_from_permit,
+};
+
+/// Per-user powerup balances as `(kind, count)` pairs.
+static USER_POWERUPS: Keymap<String, Vec<(Powerup, u32)>> = Keymap::new(b"user_powerups");
+
+fn load_balances(storage: &dyn Storage, user: &Addr) -> Vec<(Powerup, u32)> {
+    USER_POWERUPS.get(storage, &user.to_string()).unwrap_or_default()
+}
+
+fn price_of(network: &NetworkConfig, kind: &Powerup) -> Option<Uint128> {
+    network
+        .power_ups
+        .iter()
+        .find(|(p, _)| p == kind)
+        .map(|(_, price)| *price)
+}
+
+pub fn get_user_powerups(deps: Deps, env: Env, permit: Permit) -> StdResult<Vec<(Powerup, u32)>> {
+    let sender = address_from_permit(deps, &env, &permit)?;
+    Ok(load_balances(deps.storage, &sender))
+}
+
+fn credit(balances: &mut Vec<(Powerup, u32)>, kind: Powerup, amount: u32) {
+    match balances.iter_mut().find(|(p, _)| *p == kind) {
+        Some((_, count)) => *count += amount,
+        None => balances.push((kind, amount)),
+    }
+}
+
+pub fn try_buying_powerups(
+    deps: DepsMut,
+    sender: Addr,
+    powerups: Vec<Powerup>,
+    funds: Vec<Coin>,
+) -> Result<Response, ContractError> {
+    let coin = funds.first().ok_or(ContractError::BuyRejected {
+        reason: FailureReason::InsufficientFunds,
+    })?;
+    let network = crate::networks::network_config(deps.storage, &coin.denom)?;
+
+    let mut cost = Uint128::zero();
+    for kind in powerups.iter() {
+        cost += price_of(&network, kind).ok_or(ContractError::InvalidPowerupAmounts {})?;
+    }
+    if coin.amount < cost {
+        return Err(ContractError::BuyRejected {
+            reason: FailureReason::InsufficientFunds,
+        });
+    }
+
+    let mut balances = load_balances(deps.storage, &sender);
+    for kind in powerups.iter() {
+        credit(&mut balances, *kind, 1);
+    }
+    USER_POWERUPS.insert(deps.storage, &sender.to_string(), &balances)?;
+
+    let bought: Vec<String> = powerups
+        .iter()
+        .map(|p| format!("{p:?}").to_lowercase())
+        .collect();
+
+    Ok(Response::new()
+        .add_attribute("action", "buy_powerups")
+        .add_attribute("powerups", bought.join(","))
+        .add_attribute("denom", coin.denom.clone())
+        .add_attribute("paid", cost.to_string()))
+}
+
+/// Consume the powerups a player chose while opening a cell, auto-buying any
+/// they are short of when `autopay` is set and extra funds were provided.
+///
+/// `price_denom` is the denom the powerup prices in `network` are quoted in
+/// (the same denom the cell-opening payment itself arrived in); `extra_funds`
+/// is only accepted as payment when it matches.
+pub fn spend_powerups(
+    storage: &mut dyn Storage,
+    user: &Addr,
+    powerups: &[Powerup],
+    autopay: bool,
+    network: &NetworkConfig,
+    price_denom: &str,
+    extra_funds: Option<&Coin>,
+) -> Result<(), ContractError> {
+    if powerups.is_empty() {
+        return Ok(());
+    }
+
+    let mut balances = load_balances(storage, user);
+    let mut autopay_cost = Uint128::zero();
+
+    for kind in powerups {
+        match balances.iter_mut().find(|(p, c)| p == kind && *c > 0) {
+            Some((_, count)) => *count -= 1,
+            None if autopay => {
+                autopay_cost +=
+                    price_of(network, kind).ok_or(ContractError::InvalidPowerupAmounts {})?;
+            }
+            None => return Err(ContractError::MissingPowerup { kind: *kind }),
+        }
+    }
+
+    if !autopay_cost.is_zero() {
+        let paid = extra_funds
+            .filter(|c| c.denom == price_denom)
+            .map(|c| c.amount)
+            .unwrap_or_default();
+        if paid < autopay_cost {
+            return Err(ContractError::OpenRejected {
+                reason: FailureReason::InsufficientFunds,
+            });
+        }
+    }
+
+    USER_POWERUPS.insert(storage, &user.to_string(), &balances)?;
+    Ok(())
+}
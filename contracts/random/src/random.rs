@@ -0,0 +1,90 @@
+use cosmwasm_std::{
+    sha2::{Digest, Sha256},
+    BlockInfo, Deps, DepsMut, Env, Response, StdResult,
+};
+
+use rand_chacha::rand_core::CryptoRngCore;
+use secret_toolkit::{permit::Permit, storage::Keymap};
+
+use crate::{error::ContractError, utils::address_from_permit};
+
+/// Per-user random number last submitted through `UpdateMyRandomNumber`.
+static RANDOM: Keymap<String, u32> = Keymap::new(b"random");
+
+/// Number of distinct cell values. The hidden `random` byte lives in `0..255`.
+const CELL_VALUES: u32 = u8::MAX as u32;
+
+/// Seed a [`rand_chacha::ChaChaRng`] from the current block and an address.
+pub fn randomness_seed(block: &BlockInfo, sender: &str) -> [u8; 32] {
+    let mut hasher = Sha256::new();
+    hasher.update(block.height.to_be_bytes());
+    hasher.update(block.time.seconds().to_be_bytes());
+    hasher.update(block.chain_id.as_bytes());
+    hasher.update(sender.as_bytes());
+    hasher.finalize().into()
+}
+
+/// Draw a uniform cell value in `0..255` free of modulo bias.
+///
+/// `next_u32() % 255` would skew the low residues because `2^32` is not a
+/// multiple of `255`; instead we reject any draw landing in the short tail
+/// above the largest multiple of `255` before taking the remainder.
+pub fn next_cell_value(generator: &mut dyn CryptoRngCore) -> u8 {
+    let zone = (u32::MAX / CELL_VALUES) * CELL_VALUES;
+    loop {
+        let draw = generator.next_u32();
+        if draw < zone {
+            return (draw % CELL_VALUES) as u8;
+        }
+    }
+}
+
+pub fn try_saving_random_number(
+    deps: DepsMut,
+    env: Env,
+    permit: Permit,
+) -> Result<Response, ContractError> {
+    let sender = address_from_permit(deps.as_ref(), &env, &permit)?;
+
+    let mut ring = rand_chacha::ChaChaRng::from_seed(randomness_seed(
+        &env.block,
+        sender.as_str(),
+    ));
+    let number = next_cell_value(ring.as_rngcore()) as u32;
+
+    RANDOM.insert(deps.storage, &sender.into_string(), &number)?;
+    Ok(Response::default())
+}
+
+pub fn get_saved_random_number(deps: Deps, env: Env, permit: Permit) -> StdResult<u32> {
+    let sender = address_from_permit(deps, &env, &permit)?;
+    Ok(RANDOM.get(deps.storage, &sender.into_string()).unwrap_or_default())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use rand_chacha::rand_core::SeedableRng;
+
+    #[test]
+    fn cell_value_distribution_is_flat() {
+        let mut ring = rand_chacha::ChaChaRng::from_seed([7u8; 32]);
+        let generator = ring.as_rngcore();
+
+        const SAMPLES: u32 = 255 * 4_000;
+        let mut counts = [0u32; 255];
+        for _ in 0..SAMPLES {
+            counts[next_cell_value(generator) as usize] += 1;
+        }
+
+        let expected = SAMPLES as f64 / 255.0;
+        // every bucket should sit within 10% of the flat expectation
+        for (value, &count) in counts.iter().enumerate() {
+            let deviation = (count as f64 - expected).abs() / expected;
+            assert!(
+                deviation < 0.1,
+                "value {value} off by {deviation:.3} ({count} vs {expected:.0})"
+            );
+        }
+    }
+}
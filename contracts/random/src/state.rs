@@ -0,0 +1,81 @@
+use cosmwasm_std::{Addr, Uint128};
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
+
+use secret_toolkit::storage::{Item, Keymap};
+
+/// Global game configuration.
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct Config {
+    /// Cells whose `random` value, doubled, is above this threshold are
+    /// winning cells.
+    pub win_threshold: u16,
+    /// Seconds a single cell stays locked after being opened.
+    pub cell_cooldown: u64,
+    /// Seconds a single user has to wait between two openings.
+    pub user_cooldown: u64,
+    /// Root of the incremental Merkle tree committing to every cell.
+    pub merkle_root: [u8; 32],
+}
+
+/// The on-chain state of a single cell of the board.
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct CellState {
+    /// Hidden value that decides whether the cell wins.
+    pub random: u8,
+    /// Unix timestamp (seconds) at which the cell may be opened again.
+    pub open_at: u64,
+}
+
+/// Per-denom pricing and powerup catalogue of the game.
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct NetworkConfig {
+    pub chain_id: String,
+    /// IBC channel the denom arrives through, required for `ibc/` denoms.
+    pub channel_id: Option<String>,
+    /// Price of opening a single cell in this denom (shared by both payment
+    /// paths; `open_price` is the per-cell price whether funds arrive as
+    /// native coins or as a CW20 transfer).
+    pub open_price: Uint128,
+    /// CW20-style token contract allowed to pay for this network in place of
+    /// native `info.funds`. Payments are priced with `open_price`.
+    pub token_contract: Option<Addr>,
+    /// The three powerups offered on this network and their price.
+    pub power_ups: Vec<(Powerup, Uint128)>,
+}
+
+/// Powerups a player can buy and spend while opening a cell.
+///
+/// Spending a powerup only debits it from the player's balance (see
+/// [`crate::powerups::spend_powerups`]); opening a cell does not act on the
+/// kind, so no powerup changes the revealed value or the outcome.
+#[derive(Serialize, Deserialize, Clone, Copy, Debug, PartialEq, Eq, Hash, JsonSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum Powerup {
+    /// The re-roll powerup.
+    Reroll,
+    /// The shield powerup.
+    Shield,
+    /// The peek powerup.
+    Peek,
+}
+
+pub static CONFIG: Item<Config> = Item::new(b"config");
+pub static FIELD_SIZE: Item<u32> = Item::new(b"field_size");
+/// Whether the board currently accepts openings. Toggled by `SetAppStatus`.
+pub static APP_STATUS: Item<bool> = Item::new(b"app_status");
+pub static NETWORK_CONFIGS: Keymap<String, NetworkConfig> = Keymap::new(b"network_configs");
+
+/// Last time (seconds) a player opened any cell, for the per-user cooldown.
+pub static LAST_OPEN: Keymap<String, u64> = Keymap::new(b"last_open");
+
+/// Legacy per-cell store kept only so [`crate::field::migrate_field`] can
+/// rebuild the packed blobs below from an older instance.
+pub static CELLS: Keymap<u32, CellState> = Keymap::new(b"cells");
+
+/// Packed field storage: one `random` byte per cell, indexed by `cell_id - 1`.
+/// Reading or writing the whole board is two storage ops instead of one per
+/// cell — see [`crate::field`].
+pub static CELL_RANDOM: Item<Vec<u8>> = Item::new(b"cell_random");
+/// Packed `open_at` timestamps, indexed by `cell_id - 1`.
+pub static CELL_OPEN_AT: Item<Vec<u64>> = Item::new(b"cell_open_at");